@@ -1,14 +1,37 @@
-use std::sync::atomic::*;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Deref;
+use core::sync::atomic::*;
 
 macro_rules! impl_array {
-    ($name:ident, $type_name:expr, $type:ty, $atomic_type:ident) => {
-        #[doc = "A `"]
+    ($name:ident, $slice:ident, $type_name:expr, $type:ty, $atomic_type:ident, $width:tt) => {
+        #[doc = "An owned `"]
         #[doc = $type_name]
         #[doc = "` array in which elements may be updated atomically."]
+        ///
+        /// Dereferences to [`
+        #[doc = stringify!($slice)]
+        /// `], which carries the per-element operations.
+        #[cfg(target_has_atomic = $width)]
         pub struct $name {
             buf: Box<[$atomic_type]>
         }
 
+        #[doc = "A borrowed view over a `"]
+        #[doc = $type_name]
+        #[doc = "` buffer whose elements may be updated atomically."]
+        ///
+        /// This is the [`Deref`] target of the owning array, and can also be
+        /// obtained without allocating via [`from_mut`](Self::from_mut).
+        #[cfg(target_has_atomic = $width)]
+        #[repr(transparent)]
+        pub struct $slice([$atomic_type]);
+
+        // The whole array type is only emitted when the target has atomic
+        // support for this width; `target_has_atomic` is the stable cfg (the
+        // finer-grained `target_has_atomic_load_store` is still unstable).
+        #[cfg(target_has_atomic = $width)]
         impl $name {
             /// Constructs a new array with the specified length.
             /// All values will be initialized to their default.
@@ -30,57 +53,363 @@ macro_rules! impl_array {
                 }
             }
 
+            /// Reinterprets an owned boxed slice as an atomic array in place,
+            /// without reallocating or copying any elements.
+            ///
+            /// This relies on `
+            #[doc = $type_name]
+            /// ` and its atomic wrapper having identical size and alignment; the
+            /// equality is checked at compile time.
+            pub fn from_boxed(buf: Box<[$type]>) -> Self {
+                // Compile-time proof that the two representations are
+                // layout-compatible; a mismatch makes these array lengths
+                // differ and fails to type-check.
+                const _SIZE_EQ: [(); 1] =
+                    [(); (mem::size_of::<$type>() == mem::size_of::<$atomic_type>()) as usize];
+                const _ALIGN_EQ: [(); 1] =
+                    [(); (mem::align_of::<$type>() == mem::align_of::<$atomic_type>()) as usize];
+
+                // SAFETY: `$atomic_type` is guaranteed to have the same in-memory
+                // representation as `$type` (asserted above), so the backing
+                // allocation can be handed over unchanged.
+                let buf = unsafe { Box::from_raw(Box::into_raw(buf) as *mut [$atomic_type]) };
+
+                Self { buf }
+            }
+        }
+
+        #[cfg(target_has_atomic = $width)]
+        impl Deref for $name {
+            type Target = $slice;
+
+            fn deref(&self) -> &$slice {
+                // SAFETY: `$slice` is `#[repr(transparent)]` over
+                // `[$atomic_type]`, so a slice reference and a `&$slice` share
+                // the same (pointer, length) layout.
+                unsafe { &*(&*self.buf as *const [$atomic_type] as *const $slice) }
+            }
+        }
+
+        // The per-element operations live on the borrowed view so that they are
+        // reachable both through the owning array (via `Deref`) and through a
+        // view borrowed from caller-owned storage with `from_mut`.
+        #[cfg(target_has_atomic = $width)]
+        impl $slice {
+            /// Reinterprets a borrowed mutable slice as an atomic array view in
+            /// place, without reallocating, copying, or taking ownership of the
+            /// storage.
+            ///
+            /// This lets callers atomically mutate a buffer they already own (for
+            /// example a `Vec<
+            #[doc = $type_name]
+            /// >` or a memory-mapped region). Exclusive access to the slice is
+            /// required up front, but the returned borrow shares the same storage.
+            ///
+            /// This relies on `
+            #[doc = $type_name]
+            /// ` and its atomic wrapper having identical size and alignment; the
+            /// equality is checked at compile time.
+            pub fn from_mut(slice: &mut [$type]) -> &Self {
+                // Compile-time proof that the two representations are
+                // layout-compatible; a mismatch makes these array lengths
+                // differ and fails to type-check.
+                const _SIZE_EQ: [(); 1] =
+                    [(); (mem::size_of::<$type>() == mem::size_of::<$atomic_type>()) as usize];
+                const _ALIGN_EQ: [(); 1] =
+                    [(); (mem::align_of::<$type>() == mem::align_of::<$atomic_type>()) as usize];
+
+                // SAFETY: `$atomic_type` has the same in-memory representation as
+                // `$type` (asserted above) and `$slice` is `#[repr(transparent)]`
+                // over `[$atomic_type]`; the exclusive borrow guarantees no other
+                // access exists for the duration of the reinterpretation.
+                unsafe { &*(slice as *mut [$type] as *const Self) }
+            }
+
             /// Returns the number of elements in the array.
             pub fn len(&self) -> usize {
-                self.buf.len()
+                self.0.len()
             }
 
             /// Returns `true` if the array has a length of 0.
             pub fn is_empty(&self) -> bool {
-                self.buf.is_empty()
+                self.0.is_empty()
             }
 
             /// Loads and returns the value at the given position.
             ///
             /// Panics if `index` is out of bounds.
             pub fn load(&self, index: usize) -> $type {
-                self.buf[index].load(Ordering::SeqCst)
+                self.load_with(index, Ordering::SeqCst)
+            }
+
+            /// Loads and returns the value at the given position with the specified
+            /// memory ordering.
+            ///
+            /// Panics if `index` is out of bounds, or if `order` is `Release` or
+            /// `AcqRel`.
+            pub fn load_with(&self, index: usize, order: Ordering) -> $type {
+                self.0[index].load(order)
             }
 
             /// Stores the value at the given position.
             ///
             /// Panics if `index` is out bounds.
             pub fn store(&self, index: usize, value: $type) {
-                self.buf[index].store(value, Ordering::SeqCst)
+                self.store_with(index, value, Ordering::SeqCst)
+            }
+
+            /// Stores the value at the given position with the specified memory
+            /// ordering.
+            ///
+            /// Panics if `index` is out of bounds, or if `order` is `Acquire` or
+            /// `AcqRel`.
+            pub fn store_with(&self, index: usize, value: $type, order: Ordering) {
+                self.0[index].store(value, order)
             }
 
             /// Swaps the value at the given position, returning the previous value.
             ///
             /// Panics if `index` is out of bounds.
             pub fn swap(&self, index: usize, value: $type) -> $type {
-                self.buf[index].swap(value, Ordering::SeqCst)
+                self.swap_with(index, value, Ordering::SeqCst)
+            }
+
+            /// Swaps the value at the given position with the specified memory
+            /// ordering, returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn swap_with(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].swap(value, order)
+            }
+
+            /// Stores `new` at the given position if the current value is `current`,
+            /// returning the previous value.
+            ///
+            /// The return value is `Ok(current)` on success and `Err(actual)` with
+            /// the current value on failure. `success` describes the ordering used
+            /// when the comparison succeeds, `failure` the ordering used when it
+            /// fails.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn compare_exchange(
+                &self,
+                index: usize,
+                current: $type,
+                new: $type,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$type, $type> {
+                self.0[index].compare_exchange(current, new, success, failure)
+            }
+
+            /// Stores `new` at the given position if the current value is `current`.
+            ///
+            /// Unlike [`compare_exchange`], this function is allowed to fail
+            /// spuriously even when the comparison succeeds, which can yield better
+            /// performance in a compare-and-swap loop.
+            ///
+            /// [`compare_exchange`]: Self::compare_exchange
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn compare_exchange_weak(
+                &self,
+                index: usize,
+                current: $type,
+                new: $type,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$type, $type> {
+                self.0[index].compare_exchange_weak(current, new, success, failure)
+            }
+        }
+
+        /// Builds an array directly from its values, without a constructor
+        /// closure.
+        #[cfg(target_has_atomic = $width)]
+        impl<const N: usize> From<[$type; N]> for $name {
+            fn from(values: [$type; N]) -> Self {
+                Self::from_boxed(Box::new(values))
             }
         }
     };
 }
 
-impl_array!(AtomicBoolArray, "bool", bool, AtomicBool);
+/// Generates an integer array type: the shared `impl_array!` surface plus the
+/// atomic read-modify-write (`fetch_*`) family available on integer atomics.
+macro_rules! impl_int_array {
+    ($name:ident, $slice:ident, $type_name:expr, $type:ty, $atomic_type:ident, $width:tt) => {
+        impl_array!($name, $slice, $type_name, $type, $atomic_type, $width);
+
+        // The read-modify-write family lives on the borrowed view alongside the
+        // other per-element operations.
+        #[cfg(target_has_atomic = $width)]
+        impl $slice {
+            /// Adds `value` to the element at the given position, returning the
+            /// previous value. This operation wraps around on overflow.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_add(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_add(value, order)
+            }
+
+            /// Subtracts `value` from the element at the given position, returning
+            /// the previous value. This operation wraps around on overflow.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_sub(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_sub(value, order)
+            }
+
+            /// Bitwise "and" with `value` on the element at the given position,
+            /// returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_and(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_and(value, order)
+            }
+
+            /// Bitwise "or" with `value` on the element at the given position,
+            /// returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_or(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_or(value, order)
+            }
+
+            /// Bitwise "xor" with `value` on the element at the given position,
+            /// returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_xor(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_xor(value, order)
+            }
+
+            /// Bitwise "nand" with `value` on the element at the given position,
+            /// returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_nand(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_nand(value, order)
+            }
+
+            /// Stores the maximum of the current value and `value` at the given
+            /// position, returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_max(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_max(value, order)
+            }
+
+            /// Stores the minimum of the current value and `value` at the given
+            /// position, returning the previous value.
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn fetch_min(&self, index: usize, value: $type, order: Ordering) -> $type {
+                self.0[index].fetch_min(value, order)
+            }
+        }
+    };
+}
+
+impl_array!(AtomicBoolArray, AtomicBoolSlice, "bool", bool, AtomicBool, "8");
 
 #[cfg(feature = "integer_atomics")]
 mod integer {
-    impl_array!(AtomicI8Array, "i8", i8, AtomicI8);
-    impl_array!(AtomicI16Array, "i16", i16, AtomicI16);
-    impl_array!(AtomicI32Array, "i32", i32, AtomicI32);
-    impl_array!(AtomicI64Array, "i64", i64, AtomicI64);
-
-    impl_array!(AtomicU8Array, "u8", u8, AtomicU8);
-    impl_array!(AtomicU16Array, "u16", u16, AtomicU16);
-    impl_array!(AtomicU32Array, "u32", u32, AtomicU32);
-    impl_array!(AtomicU64Array, "u64", u64, AtomicU64);
+    impl_int_array!(AtomicI8Array, AtomicI8Slice, "i8", i8, AtomicI8, "8");
+    impl_int_array!(AtomicI16Array, AtomicI16Slice, "i16", i16, AtomicI16, "16");
+    impl_int_array!(AtomicI32Array, AtomicI32Slice, "i32", i32, AtomicI32, "32");
+    impl_int_array!(AtomicI64Array, AtomicI64Slice, "i64", i64, AtomicI64, "64");
+
+    impl_int_array!(AtomicU8Array, AtomicU8Slice, "u8", u8, AtomicU8, "8");
+    impl_int_array!(AtomicU16Array, AtomicU16Slice, "u16", u16, AtomicU16, "16");
+    impl_int_array!(AtomicU32Array, AtomicU32Slice, "u32", u32, AtomicU32, "32");
+    impl_int_array!(AtomicU64Array, AtomicU64Slice, "u64", u64, AtomicU64, "64");
 }
 
 #[cfg(feature = "integer_atomics")]
 use self::integer::*;
 
-impl_array!(AtomicUsizeArray, "usize", usize, AtomicUsize);
-impl_array!(AtomicIsizeArray, "isize", isize, AtomicIsize);
+impl_int_array!(AtomicUsizeArray, AtomicUsizeSlice, "usize", usize, AtomicUsize, "ptr");
+impl_int_array!(AtomicIsizeArray, AtomicIsizeSlice, "isize", isize, AtomicIsize, "ptr");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_boxed_round_trips() {
+        let buf: Box<[usize]> = Box::new([4, 5, 6]);
+        let array = AtomicUsizeArray::from_boxed(buf);
+
+        assert_eq!(array.len(), 3);
+        assert!(!array.is_empty());
+        assert_eq!(array.load(0), 4);
+        assert_eq!(array.load(2), 6);
+    }
+
+    #[test]
+    fn from_array_literal() {
+        let array = AtomicUsizeArray::from([7, 8, 9]);
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.load(2), 9);
+    }
+
+    #[test]
+    fn from_mut_mutates_caller_storage_in_place() {
+        let mut data = [1usize, 2, 3];
+
+        {
+            let view = AtomicUsizeSlice::from_mut(&mut data);
+
+            assert_eq!(view.len(), 3);
+            assert_eq!(view.swap(0, 10), 1);
+            assert_eq!(view.fetch_add(1, 5, Ordering::SeqCst), 2);
+        }
+
+        // The view wrote through to the borrowed buffer, with no copy-back.
+        assert_eq!(data, [10, 7, 3]);
+    }
+
+    #[test]
+    fn fetch_add_wraps_on_overflow() {
+        let array = AtomicUsizeArray::new(1);
+        array.store(0, usize::MAX);
+
+        assert_eq!(array.fetch_add(0, 1, Ordering::SeqCst), usize::MAX);
+        assert_eq!(array.load(0), 0);
+    }
+
+    #[test]
+    fn fetch_bitwise_and_minmax() {
+        let array = AtomicUsizeArray::from([0b1100]);
+
+        assert_eq!(array.fetch_or(0, 0b0010, Ordering::SeqCst), 0b1100);
+        assert_eq!(array.fetch_and(0, 0b0110, Ordering::SeqCst), 0b1110);
+        assert_eq!(array.load(0), 0b0110);
+
+        assert_eq!(array.fetch_max(0, 0b1111, Ordering::SeqCst), 0b0110);
+        assert_eq!(array.load(0), 0b1111);
+        assert_eq!(array.fetch_min(0, 0b0001, Ordering::SeqCst), 0b1111);
+        assert_eq!(array.load(0), 0b0001);
+    }
+
+    #[test]
+    fn compare_exchange_success_and_failure() {
+        let array = AtomicUsizeArray::from([1, 2, 3]);
+
+        assert_eq!(
+            array.compare_exchange(0, 1, 10, Ordering::SeqCst, Ordering::SeqCst),
+            Ok(1)
+        );
+        assert_eq!(array.load(0), 10);
+
+        // The stored value is now 10, so a swap expecting 1 must fail and
+        // report the actual current value without mutating it.
+        assert_eq!(
+            array.compare_exchange(0, 1, 20, Ordering::SeqCst, Ordering::SeqCst),
+            Err(10)
+        );
+        assert_eq!(array.load(0), 10);
+    }
+}
@@ -1,8 +1,17 @@
 //! Defines several array types in which elements may be updated atomically.
 //! Intended to provide atomic array types similar to those found in [java.util.concurrent.atomic](https://docs.oracle.com/javase/7/docs/api/java/util/concurrent/atomic/package-summary.html) in Java.
+//!
+//! Each `bool` and integer array type is only emitted when the target has
+//! atomic support for the relevant width (the stable `target_has_atomic` cfg),
+//! so the crate degrades gracefully on targets that lack wider atomics and can
+//! be used in `#![no_std]` contexts. The reference arrays are always emitted;
+//! only their `swap` requires pointer-width atomics.
+#![cfg_attr(not(feature = "std"), no_std)]
 #[cfg_attr(feature = "integer_atomics", feature(integer_atomics))]
 extern crate atomic_ref2;
 
+extern crate alloc;
+
 mod array;
 mod option_ref_array;
 mod ref_array;
@@ -1,5 +1,8 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use atomic_ref2::{AtomicOptionRef, IntoOptionArc};
-use std::sync::Arc;
+use core::iter::FromIterator;
 
 /// An array of references in which elements may be updated and retrieved atomically.
 ///
@@ -65,7 +68,19 @@ impl<T> AtomicOptionRefArray<T> {
     /// Swaps the value at the given position, returning the previous value.
     ///
     /// Panics if `index` is out of bounds.
+    #[cfg(target_has_atomic = "ptr")]
     pub fn swap(&self, index: usize, value: impl IntoOptionArc<T>) -> Option<Arc<T>> {
         self.buf[index].swap(value)
     }
 }
+
+/// Builds an array sized to the contents of the iterator.
+impl<T, U: IntoOptionArc<T>> FromIterator<U> for AtomicOptionRefArray<T> {
+    fn from_iter<I: IntoIterator<Item = U>>(iter: I) -> Self {
+        let buf: Vec<_> = iter.into_iter().map(AtomicOptionRef::from).collect();
+
+        Self {
+            buf: buf.into_boxed_slice(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::AtomicOptionRefArray;
-use std::sync::Arc;
+use alloc::sync::Arc;
+use core::iter::FromIterator;
 
 /// An array of non-optional references in which elements may be updated and retrieved atomically.
 pub struct AtomicRefArray<T> {
@@ -54,7 +55,25 @@ impl<T> AtomicRefArray<T> {
     /// Swaps the value at the given position, returning the previous value.
     ///
     /// Panics if `index` is out of bounds.
+    #[cfg(target_has_atomic = "ptr")]
     pub fn swap(&self, index: usize, value: impl Into<Arc<T>>) -> Arc<T> {
         self.buf.swap(index, value.into()).unwrap()
     }
 }
+
+/// Builds an array sized to the contents of the iterator.
+impl<T, U: Into<Arc<T>>> FromIterator<U> for AtomicRefArray<T> {
+    fn from_iter<I: IntoIterator<Item = U>>(iter: I) -> Self {
+        Self {
+            buf: iter.into_iter().map(|value| value.into()).collect(),
+        }
+    }
+}
+
+/// Builds an array directly from an array of values, e.g.
+/// `AtomicRefArray::from(["a".to_owned(), "b".to_owned()])`.
+impl<T, U: Into<Arc<T>>, const N: usize> From<[U; N]> for AtomicRefArray<T> {
+    fn from(values: [U; N]) -> Self {
+        Self::from_iter(values)
+    }
+}